@@ -3,7 +3,7 @@
 //!
 
 use crate::{two, Components, Signature, OID};
-use core::cmp::min;
+use core::fmt;
 use crypto_bigint::{
     modular::{BoxedMontyForm, BoxedMontyParams},
     BoxedUint, InvMod, NonZero, Odd,
@@ -14,10 +14,60 @@ use pkcs8::{
         asn1::{BitStringRef, UintRef},
         AnyRef, Decode, Encode,
     },
-    spki, AlgorithmIdentifierRef, EncodePublicKey, SubjectPublicKeyInfoRef,
+    spki, AlgorithmIdentifierRef, EncodePublicKey, ObjectIdentifier, SubjectPublicKeyInfoRef,
 };
 use signature::{hazmat::PrehashVerifier, DigestVerifier, Verifier};
 
+/// Errors that can occur while constructing or parsing a [`VerifyingKey`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum VerifyingKeyError {
+    /// The public component `y` was smaller than `2` or not smaller than `p`.
+    YOutOfRange,
+    /// The public component `y` was not a member of the order-`q` subgroup of `p`.
+    NotInSubgroup,
+    /// The algorithm OID in the `AlgorithmIdentifier` did not match the DSA OID. Carries the
+    /// OID that was actually found.
+    AlgorithmMismatch(ObjectIdentifier),
+    /// The DER-encoded key could not be decoded.
+    Malformed,
+}
+
+impl fmt::Display for VerifyingKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::YOutOfRange => f.write_str("public component `y` out of range"),
+            Self::NotInSubgroup => {
+                f.write_str("public component `y` is not in the order-`q` subgroup")
+            }
+            Self::AlgorithmMismatch(oid) => {
+                write!(f, "unexpected algorithm OID `{oid}` (expected the DSA OID)")
+            }
+            Self::Malformed => f.write_str("malformed DER-encoded key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifyingKeyError {}
+
+impl From<VerifyingKeyError> for signature::Error {
+    fn from(_: VerifyingKeyError) -> Self {
+        signature::Error::new()
+    }
+}
+
+impl From<VerifyingKeyError> for spki::Error {
+    fn from(err: VerifyingKeyError) -> Self {
+        match err {
+            VerifyingKeyError::AlgorithmMismatch(oid) => spki::Error::OidUnknown { oid },
+            VerifyingKeyError::YOutOfRange
+            | VerifyingKeyError::NotInSubgroup
+            | VerifyingKeyError::Malformed => spki::Error::KeyMalformed,
+        }
+    }
+}
+
 /// DSA public key.
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 #[must_use]
@@ -34,12 +84,16 @@ impl VerifyingKey {
     pub fn from_components(
         components: Components,
         y: NonZero<BoxedUint>,
-    ) -> signature::Result<Self> {
+    ) -> Result<Self, VerifyingKeyError> {
+        if *y < two() || *y >= **components.p() {
+            return Err(VerifyingKeyError::YOutOfRange);
+        }
+
         let params = BoxedMontyParams::new_vartime(Odd::new((**components.p()).clone()).unwrap());
         let form = BoxedMontyForm::new((*y).clone(), params);
 
-        if *y < two() || form.pow(components.q()).retrieve() != BoxedUint::one() {
-            return Err(signature::Error::new());
+        if form.pow(components.q()).retrieve() != BoxedUint::one() {
+            return Err(VerifyingKeyError::NotInSubgroup);
         }
 
         Ok(Self { components, y })
@@ -56,6 +110,51 @@ impl VerifyingKey {
         &self.y
     }
 
+    /// Encode the public component `y` as a fixed-width big-endian byte string, zero-padded
+    /// on the left to the byte length of `p`.
+    ///
+    /// This mirrors the compact fixed-size key representations used by other signature
+    /// crates, for embedding DSA public keys in binary protocols where the verbose SPKI DER
+    /// wrapper produced by [`to_public_key_der`](EncodePublicKey::to_public_key_der) is unwanted.
+    #[must_use]
+    pub fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        let width = (self.components.p().bits() as usize).div_ceil(8);
+        let y_bytes = self.y.to_be_bytes();
+
+        // `to_be_bytes` reflects `y`'s allocated precision, which can carry extra leading
+        // zero limbs beyond what `p`'s bit length implies even though `y < p` is enforced by
+        // `from_components`. Take only the low `width` bytes rather than assuming `y_bytes`
+        // is no longer than `width`, or this underflows whenever it is.
+        let minimal = match y_bytes.len().checked_sub(width) {
+            Some(skip) => &y_bytes[skip..],
+            None => &y_bytes[..],
+        };
+
+        let mut out = alloc::vec![0u8; width];
+        let offset = width - minimal.len();
+        out[offset..].copy_from_slice(minimal);
+        out
+    }
+
+    /// Reconstruct a public key from the fixed-width raw bytes produced by [`Self::to_bytes`]
+    /// and the common components it was encoded with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` decodes to a `y` that fails the same subgroup validation
+    /// performed by [`Self::from_components`].
+    pub fn from_bytes(
+        bytes: &[u8],
+        components: Components,
+    ) -> Result<Self, VerifyingKeyError> {
+        let y = BoxedUint::from_be_slice(bytes, bytes.len() as u32 * 8)
+            .map_err(|_| VerifyingKeyError::Malformed)?;
+        let y = NonZero::new(y);
+        let y = Option::from(y).ok_or(VerifyingKeyError::YOutOfRange)?;
+
+        Self::from_components(components, y)
+    }
+
     /// Verify some prehashed data
     #[must_use]
     fn verify_prehashed(&self, hash: &[u8], signature: &Signature) -> Option<bool> {
@@ -70,30 +169,58 @@ impl VerifyingKey {
 
         let w = Option::from(s.inv_mod(q))?;
 
-        let n = q.bits() / 8;
-        let block_size = hash.len(); // Hash function output size
+        // FIPS 186-4, Section 4.6: `z` is the leftmost `min(N, outlen)` *bits* of the hash,
+        // where `N` is the bit length of `q` and `outlen` is the hash's output bit length.
+        // When `N < outlen` and `N` is not a multiple of 8, this is not a whole number of bytes,
+        // so the hash has to be treated as one big big-endian integer and shifted down rather
+        // than simply truncated to `min(N, outlen) / 8` bytes.
+        let n = q.bits();
+        let outlen = hash.len() as u32 * 8;
 
-        let z_len = min(n as usize, block_size);
-        let z = BoxedUint::from_be_slice(&hash[..z_len], z_len as u32 * 8).unwrap();
+        let full_hash = BoxedUint::from_be_slice(hash, outlen).unwrap();
+        let z = if outlen > n {
+            full_hash >> (outlen - n)
+        } else {
+            full_hash
+        };
 
         let u1 = (&z * &w) % q;
         let u2 = r.mul_mod(&w, q);
 
-        let u1_params = BoxedMontyParams::new(Odd::new(u1).unwrap());
-        let u2_params = BoxedMontyParams::new(Odd::new(u2).unwrap());
+        // v = (g^u1 * y^u2 mod p) mod q -- both exponentiations share the modulus p, not u1/u2.
+        let p_params = BoxedMontyParams::new_vartime(Odd::new((**p).clone()).unwrap());
 
-        let g_form = BoxedMontyForm::new((**g).clone(), u1_params);
-        let y_form = BoxedMontyForm::new((**y).clone(), u2_params);
+        let g_form = BoxedMontyForm::new((**g).clone(), p_params.clone());
+        let y_form = BoxedMontyForm::new((**y).clone(), p_params);
 
-        let v = (g_form.pow(p).retrieve() * y_form.pow(p).retrieve() % p) % q;
+        let v = (g_form.pow(&u1).retrieve() * y_form.pow(&u2).retrieve() % p) % q;
 
         Some(v == **r)
     }
+
+    /// Finish verification of an already-computed hash, turning the `verify_prehashed` output
+    /// into the `signature::Error` every public `Verifier`-family impl on this type returns.
+    fn finish_verify(&self, hash: &[u8], signature: &Signature) -> Result<(), signature::Error> {
+        if let Some(true) = self.verify_prehashed(hash, signature) {
+            Ok(())
+        } else {
+            Err(signature::Error::new())
+        }
+    }
 }
 
 impl Verifier<Signature> for VerifyingKey {
+    /// Verify a signature over `msg`, hashing it with the digest FIPS 186-4 pairs with the bit
+    /// length `N` of `q` (`N <= 160` -> SHA-1, `N <= 224` -> SHA-224, `N <= 256` -> SHA-256,
+    /// `N <= 384` -> SHA-384, otherwise SHA-512).
     fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), signature::Error> {
-        self.verify_digest(sha2::Sha256::new_with_prefix(msg), signature)
+        match self.components.q().bits() {
+            n if n <= 160 => self.finish_verify(&sha1::Sha1::digest(msg), signature),
+            n if n <= 224 => self.finish_verify(&sha2::Sha224::digest(msg), signature),
+            n if n <= 256 => self.finish_verify(&sha2::Sha256::digest(msg), signature),
+            n if n <= 384 => self.finish_verify(&sha2::Sha384::digest(msg), signature),
+            _ => self.finish_verify(&sha2::Sha512::digest(msg), signature),
+        }
     }
 }
 
@@ -103,11 +230,7 @@ impl PrehashVerifier<Signature> for VerifyingKey {
         prehash: &[u8],
         signature: &Signature,
     ) -> Result<(), signature::Error> {
-        if let Some(true) = self.verify_prehashed(prehash, signature) {
-            Ok(())
-        } else {
-            Err(signature::Error::new())
-        }
+        self.finish_verify(prehash, signature)
     }
 }
 
@@ -116,17 +239,7 @@ where
     D: Digest,
 {
     fn verify_digest(&self, digest: D, signature: &Signature) -> Result<(), signature::Error> {
-        let hash = digest.finalize();
-
-        let is_valid = self
-            .verify_prehashed(&hash, signature)
-            .ok_or_else(signature::Error::new)?;
-
-        if !is_valid {
-            return Err(signature::Error::new());
-        }
-
-        Ok(())
+        self.finish_verify(&digest.finalize(), signature)
     }
 }
 
@@ -155,16 +268,24 @@ impl<'a> TryFrom<SubjectPublicKeyInfoRef<'a>> for VerifyingKey {
     type Error = spki::Error;
 
     fn try_from(value: SubjectPublicKeyInfoRef<'a>) -> Result<Self, Self::Error> {
-        value.algorithm.assert_algorithm_oid(OID)?;
+        if value.algorithm.oid != OID {
+            return Err(VerifyingKeyError::AlgorithmMismatch(value.algorithm.oid).into());
+        }
 
-        let parameters = value.algorithm.parameters_any()?;
-        let components = parameters.decode_as()?;
+        let parameters = value
+            .algorithm
+            .parameters_any()
+            .map_err(|_| VerifyingKeyError::Malformed)?;
+        let components = parameters
+            .decode_as()
+            .map_err(|_| VerifyingKeyError::Malformed)?;
         let y = UintRef::from_der(
             value
                 .subject_public_key
                 .as_bytes()
-                .ok_or(spki::Error::KeyMalformed)?,
-        )?;
+                .ok_or(VerifyingKeyError::Malformed)?,
+        )
+        .map_err(|_| VerifyingKeyError::Malformed)?;
 
         Self::from_components(
             components,
@@ -173,6 +294,219 @@ impl<'a> TryFrom<SubjectPublicKeyInfoRef<'a>> for VerifyingKey {
             )
             .unwrap(),
         )
-        .map_err(|_| spki::Error::KeyMalformed)
+        .map_err(Into::into)
+    }
+}
+
+/// Serde support for [`VerifyingKey`].
+///
+/// Human-readable formats (JSON, TOML, ...) encode the key as the Base64 of its SPKI DER
+/// representation, matching the convention used by TUF-style key objects. Binary formats
+/// (CBOR, bincode, ...) instead embed the raw DER bytes directly, since they have no need
+/// for a text-safe encoding.
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::VerifyingKey;
+    use alloc::vec::Vec;
+    use base64ct::{Base64, Encoding};
+    use core::fmt;
+    use pkcs8::{EncodePublicKey, SubjectPublicKeyInfoRef};
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    impl Serialize for VerifyingKey {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let der = self.to_public_key_der().map_err(ser::Error::custom)?;
+
+            if serializer.is_human_readable() {
+                serializer.serialize_str(&Base64::encode_string(der.as_bytes()))
+            } else {
+                serializer.serialize_bytes(der.as_bytes())
+            }
+        }
+    }
+
+    /// Accepts either a Base64 string (human-readable formats) or raw DER bytes (binary
+    /// formats), since binary formats such as CBOR distinguish a byte string from a sequence
+    /// and may hand either `visit_bytes` or `visit_byte_buf` a borrowed/owned buffer.
+    struct DerVisitor;
+
+    impl<'de> de::Visitor<'de> for DerVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a Base64-encoded SPKI DER string, or raw SPKI DER bytes")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Base64::decode_vec(v).map_err(E::custom)
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for VerifyingKey {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let der = if deserializer.is_human_readable() {
+                deserializer.deserialize_str(DerVisitor)?
+            } else {
+                deserializer.deserialize_bytes(DerVisitor)?
+            };
+
+            let spki = SubjectPublicKeyInfoRef::try_from(der.as_slice())
+                .map_err(de::Error::custom)?;
+
+            Self::try_from(spki).map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    fn uint(bytes: &[u8]) -> NonZero<BoxedUint> {
+        NonZero::new(BoxedUint::from_be_slice(bytes, bytes.len() as u32 * 8).unwrap()).unwrap()
+    }
+
+    /// Toy 256-bit/152-bit DSA domain parameters and keypair, reused by several tests below.
+    fn sample_key() -> VerifyingKey {
+        let p = uint(&hex!(
+            "96791645aafdb5f0383e44b1a1c97331dbe9ba92d9ef70113e0263493592a35b"
+        ));
+        let q = uint(&hex!("c386bbcd613e30d8f16adf91b7584a2265b2af"));
+        let g = uint(&hex!(
+            "9655c1a57619b5536dd79574135c54122aec9a58cd477169860fbeb497406ff4"
+        ));
+        let y = uint(&hex!(
+            "4e5e312c6f75ec944c07415739bdc53b72e8019b8088e644c7acfeabe81a967d"
+        ));
+
+        let components = Components::from_components(p, q, g).unwrap();
+        VerifyingKey::from_components(components, y).unwrap()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip_human_readable() {
+        let key = sample_key();
+
+        let json = serde_json::to_string(&key).unwrap();
+        let decoded: VerifyingKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, key);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip_binary() {
+        use alloc::vec::Vec;
+
+        let key = sample_key();
+
+        let mut buf = Vec::new();
+        ciborium::into_writer(&key, &mut buf).unwrap();
+        let decoded: VerifyingKey = ciborium::from_reader(buf.as_slice()).unwrap();
+        assert_eq!(decoded, key);
+    }
+
+    #[test]
+    fn to_bytes_does_not_panic_on_over_precise_y() {
+        let p = uint(&hex!(
+            "96791645aafdb5f0383e44b1a1c97331dbe9ba92d9ef70113e0263493592a35b"
+        ));
+        let q = uint(&hex!("c386bbcd613e30d8f16adf91b7584a2265b2af"));
+        let g = uint(&hex!(
+            "9655c1a57619b5536dd79574135c54122aec9a58cd477169860fbeb497406ff4"
+        ));
+        let components = Components::from_components(p, q, g).unwrap();
+
+        // `y` carries one extra leading zero byte of precision beyond what `p`'s byte length
+        // implies (as e.g. a `widen`-style operation elsewhere in a pipeline might produce).
+        // The *value* is still < p; `to_bytes` used to panic on this via a length subtraction
+        // underflow.
+        let padded_y_bytes =
+            hex!("004e5e312c6f75ec944c07415739bdc53b72e8019b8088e644c7acfeabe81a967d");
+        let y = NonZero::new(
+            BoxedUint::from_be_slice(&padded_y_bytes, padded_y_bytes.len() as u32 * 8).unwrap(),
+        )
+        .unwrap();
+
+        let key = VerifyingKey::from_components(components.clone(), y).unwrap();
+
+        let width = (components.p().bits() as usize).div_ceil(8);
+        let bytes = key.to_bytes();
+        assert_eq!(bytes.len(), width);
+        assert_eq!(
+            &*bytes,
+            &hex!("4e5e312c6f75ec944c07415739bdc53b72e8019b8088e644c7acfeabe81a967d")
+        );
+
+        let round_tripped = VerifyingKey::from_bytes(&bytes, components).unwrap();
+        assert_eq!(round_tripped.y(), key.y());
+    }
+
+    /// Independently computed (Python, not this crate) known-answer vector for `N = 152` — a
+    /// bit length `<= 160` (selecting the SHA-1 branch) and shorter than SHA-1's 160-bit
+    /// output, so the hash is truncated to its leftmost 152 bits.
+    #[test]
+    fn verify_known_answer_n152_sha1() {
+        let key = sample_key();
+
+        let r = uint(&hex!("ad5a8800575b1b88ed7e25f6f9d8eaf6b57c0b"));
+        let s = uint(&hex!("35a7f0294c0e3858d4241a1ea2da1d339029b8"));
+        let signature = Signature::from_components(r, s).unwrap();
+
+        key.verify(b"known answer test message 1", &signature)
+            .unwrap();
+    }
+
+    /// Independently computed (Python, not this crate) known-answer vector for `N = 179` —
+    /// between 161 and 224 bits, selecting the SHA-224 branch, and not byte-aligned.
+    #[test]
+    fn verify_known_answer_n179_sha224() {
+        let p = uint(&hex!(
+            "8fb2b308e018254a6628a54553b15326b4d6fb2cfeba99dfe8baced3625bcb2\
+             888eed39eb8d6702095c408a9b12eda75"
+        ));
+        let q = uint(&hex!("04bb900e7a269fd95bafc8f2a4d27bdcf4bb99f4bea987"));
+        let g = uint(&hex!(
+            "7bb432f76a1622f359fe76fa1f30fa164031035c35a2626318f36a4dd149e0a\
+             9abfe97e7f64178daad30b5aa100ae571"
+        ));
+        let y = uint(&hex!(
+            "519021e159d32e0c81e582412bd66cb13cef2ee3340640ef84e23f107c30061\
+             ef50c46588673041099daf4a6859b47dc"
+        ));
+
+        let components = Components::from_components(p, q, g).unwrap();
+        let key = VerifyingKey::from_components(components, y).unwrap();
+
+        let r = uint(&hex!("030a2240393a2555f6dd1d6f8c5d2b0c312a359b2566a8"));
+        let s = uint(&hex!("047f5da64f85b64dbb42fe7524bde5163748e92ff3ea6a"));
+        let signature = Signature::from_components(r, s).unwrap();
+
+        key.verify(b"known answer test message 2", &signature)
+            .unwrap();
     }
 }